@@ -0,0 +1,169 @@
+use crate::{Error, RgbCGain, RgbCInterruptPersistence, Tcs3400};
+use embedded_hal::blocking::i2c;
+
+/// A full device configuration, built up with the `with_*` methods and
+/// applied in a single call to [`Tcs3400::apply_config()`].
+///
+/// `Config::default()` matches the chip's power-on defaults, so a config
+/// built from it and modified with a few `with_*` calls only needs to
+/// describe what differs from power-on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub(crate) gain: RgbCGain,
+    pub(crate) integration_cycles: u16,
+    pub(crate) wait_cycles: u16,
+    pub(crate) wait_long: bool,
+    pub(crate) rgbc_interrupt_low_threshold: u16,
+    pub(crate) rgbc_interrupt_high_threshold: u16,
+    pub(crate) rgbc_interrupt_persistence: RgbCInterruptPersistence,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            gain: RgbCGain::_1x,
+            integration_cycles: 1,
+            wait_cycles: 1,
+            wait_long: false,
+            rgbc_interrupt_low_threshold: 0,
+            rgbc_interrupt_high_threshold: 0,
+            rgbc_interrupt_persistence: RgbCInterruptPersistence::Every,
+        }
+    }
+}
+
+impl Config {
+    /// Set the RGB converter gain.
+    pub fn with_gain(mut self, gain: RgbCGain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Set the number of integration cycles (1-256).
+    pub fn with_integration_cycles(mut self, cycles: u16) -> Self {
+        self.integration_cycles = cycles;
+        self
+    }
+
+    /// Set the number of wait cycles (1-256).
+    pub fn with_wait_cycles(mut self, cycles: u16) -> Self {
+        self.wait_cycles = cycles;
+        self
+    }
+
+    /// Enable or disable the "*wait long*" `x12` multiplier.
+    pub fn with_wait_long(mut self, wait_long: bool) -> Self {
+        self.wait_long = wait_long;
+        self
+    }
+
+    /// Set the RGB converter interrupt clear channel low threshold.
+    pub fn with_rgbc_interrupt_low_threshold(mut self, threshold: u16) -> Self {
+        self.rgbc_interrupt_low_threshold = threshold;
+        self
+    }
+
+    /// Set the RGB converter interrupt clear channel high threshold.
+    pub fn with_rgbc_interrupt_high_threshold(mut self, threshold: u16) -> Self {
+        self.rgbc_interrupt_high_threshold = threshold;
+        self
+    }
+
+    /// Set the RGB converter interrupt persistence.
+    pub fn with_rgbc_interrupt_persistence(
+        mut self,
+        persistence: RgbCInterruptPersistence,
+    ) -> Self {
+        self.rgbc_interrupt_persistence = persistence;
+        self
+    }
+}
+
+impl<I2C, E> Tcs3400<I2C>
+where
+    I2C: i2c::Write<Error = E>,
+{
+    /// Apply a full configuration, writing only the registers whose value
+    /// differs from the cached configuration, to cut down on bus traffic
+    /// when reconfiguring.
+    ///
+    /// The cache compared against is `self.config`, which every individual
+    /// setter (`set_rgbc_gain()`, `set_integration_cycles()`,
+    /// `enable_wait_long()`, the threshold and persistence setters, ...)
+    /// keeps up to date as the single source of truth, so calling those
+    /// directly and then `apply_config()` still sees an accurate diff.
+    pub fn apply_config(&mut self, config: &Config) -> Result<(), Error<E>> {
+        let current = self.config;
+
+        if config.gain != current.gain {
+            self.set_rgbc_gain(config.gain)?;
+        }
+        if config.integration_cycles != current.integration_cycles {
+            self.set_integration_cycles(config.integration_cycles)?;
+        }
+        if config.wait_cycles != current.wait_cycles {
+            self.set_wait_cycles(config.wait_cycles)?;
+        }
+        if config.wait_long != current.wait_long {
+            if config.wait_long {
+                self.enable_wait_long()?;
+            } else {
+                self.disable_wait_long()?;
+            }
+        }
+        if config.rgbc_interrupt_low_threshold != current.rgbc_interrupt_low_threshold {
+            self.set_rgbc_interrupt_low_threshold(config.rgbc_interrupt_low_threshold)?;
+        }
+        if config.rgbc_interrupt_high_threshold != current.rgbc_interrupt_high_threshold {
+            self.set_rgbc_interrupt_high_threshold(config.rgbc_interrupt_high_threshold)?;
+        }
+        if config.rgbc_interrupt_persistence != current.rgbc_interrupt_persistence {
+            self.set_rgbc_interrupt_persistence(config.rgbc_interrupt_persistence)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read back the cached view of the device configuration, as last
+    /// written by [`apply_config()`](Tcs3400::apply_config) or by the
+    /// individual setters.
+    pub fn config(&self) -> Config {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Register, DEVICE_ADDRESS};
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn apply_config_only_writes_changed_registers() {
+        let expected = [I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONTROL, 2],
+        )];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        let config = Config::default().with_gain(RgbCGain::_16x);
+        sensor.apply_config(&config).unwrap();
+        assert_eq!(sensor.config(), config);
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn apply_config_overwrites_state_left_stale_by_a_direct_setter() {
+        // Regression test: a direct set_rgbc_gain() call used to leave
+        // self.config stale, so a later apply_config(&Config::default())
+        // silently skipped writing the gain back to its default value.
+        let expected = [
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONTROL, 3]),
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONTROL, 0]),
+        ];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        sensor.set_rgbc_gain(RgbCGain::_60x).unwrap();
+        sensor.apply_config(&Config::default()).unwrap();
+        assert_eq!(sensor.config(), Config::default());
+        sensor.destroy().done();
+    }
+}
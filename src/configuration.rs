@@ -1,7 +1,16 @@
+use crate::encoding::{cycles_to_register_value, rgbc_gain_value, rgbc_interrupt_persistence_value};
 use crate::{
     BitFlags, Error, Register, RgbCGain, RgbCInterruptPersistence, Tcs3400, DEVICE_ADDRESS,
 };
 use embedded_hal::blocking::i2c;
+use embedded_time::duration::{Microseconds, Nanoseconds};
+
+/// Duration of a single integration/wait cycle: 2.78 ms.
+const CYCLE_TIME: Nanoseconds<u64> = Nanoseconds(2_780_000);
+
+/// The "*wait long*" setting multiplies the configured wait time by this
+/// factor. See [`enable_wait_long()`](Tcs3400::enable_wait_long).
+const WAIT_LONG_FACTOR: u64 = 12;
 
 impl<I2C, E> Tcs3400<I2C>
 where
@@ -77,8 +86,45 @@ where
         if cycles > 256 || cycles == 0 {
             return Err(Error::InvalidInputData);
         }
-        // the value is stored as a two's complement
-        self.write_register(Register::WTIME, (256 - cycles as u16) as u8)
+        self.write_register(Register::WTIME, cycles_to_register_value(cycles))?;
+        self.config.wait_cycles = cycles;
+        Ok(())
+    }
+
+    /// Set the wait time as a duration instead of a raw cycle count.
+    ///
+    /// The requested duration is rounded to the nearest multiple of the
+    /// 2.78 ms cycle time. If it is longer than what 256 short cycles can
+    /// represent, the "*wait long*" setting is enabled automatically so
+    /// the requested duration can still be reached with the `x12`
+    /// multiplier; otherwise "*wait long*" is disabled. Returns
+    /// [`Error::InvalidInputData`] if the duration is zero or longer than
+    /// 256 long cycles (about 8.5 s) can represent.
+    pub fn set_wait_time(&mut self, time: Microseconds<u32>) -> Result<(), Error<E>> {
+        let short_max = CYCLE_TIME * 256;
+        let nanos = Nanoseconds::<u64>::from(time);
+        if nanos <= short_max {
+            let cycles = Self::duration_to_cycles(nanos, 1)?;
+            self.disable_wait_long()?;
+            self.set_wait_cycles(cycles)
+        } else {
+            let cycles = Self::duration_to_cycles(nanos, WAIT_LONG_FACTOR)?;
+            self.enable_wait_long()?;
+            self.set_wait_cycles(cycles)
+        }
+    }
+
+    /// Round a duration to the nearest number of `CYCLE_TIME * factor`
+    /// cycles. Returns [`Error::InvalidInputData`] if the rounded cycle
+    /// count falls outside the valid 1-256 range.
+    fn duration_to_cycles(nanos: Nanoseconds<u64>, factor: u64) -> Result<u16, Error<E>> {
+        let cycle_time = CYCLE_TIME * factor;
+        let half_cycle = cycle_time.0 / 2;
+        let cycles = (nanos.0 + half_cycle) / cycle_time.0;
+        if cycles == 0 || cycles > 256 {
+            return Err(Error::InvalidInputData);
+        }
+        Ok(cycles as u16)
     }
 
     /// Enable the *wait long* setting.
@@ -86,7 +132,9 @@ where
     /// The wait time configured with `set_wait_cycles()` is increased by a
     /// factor of 12. See [`set_wait_cycles()`](#method.set_wait_cycles).
     pub fn enable_wait_long(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::CONFIG, BitFlags::WLONG)
+        self.write_register(Register::CONFIG, BitFlags::WLONG)?;
+        self.config.wait_long = true;
+        Ok(())
     }
 
     /// Disable the *wait long* setting.
@@ -94,18 +142,16 @@ where
     /// The wait time configured with `set_wait_cycles()` is used without
     /// multiplication factor. See [`set_wait_cycles()`](#method.set_wait_cycles).
     pub fn disable_wait_long(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::CONFIG, 0)
+        self.write_register(Register::CONFIG, 0)?;
+        self.config.wait_long = false;
+        Ok(())
     }
 
     /// Set the RGB converter gain.
     pub fn set_rgbc_gain(&mut self, gain: RgbCGain) -> Result<(), Error<E>> {
-        // Register field: AGAIN
-        match gain {
-            RgbCGain::_1x => self.write_register(Register::CONTROL, 0),
-            RgbCGain::_4x => self.write_register(Register::CONTROL, 1),
-            RgbCGain::_16x => self.write_register(Register::CONTROL, 2),
-            RgbCGain::_60x => self.write_register(Register::CONTROL, 3),
-        }
+        self.write_register(Register::CONTROL, rgbc_gain_value(gain))?;
+        self.config.gain = gain;
+        Ok(())
     }
 
     /// Set the number of integration cycles (1-256).
@@ -115,20 +161,37 @@ where
         if cycles > 256 || cycles == 0 {
             return Err(Error::InvalidInputData);
         }
-        // the value is stored as a two's complement
-        self.write_register(Register::ATIME, (256 - cycles as u16) as u8)
+        self.write_register(Register::ATIME, cycles_to_register_value(cycles))?;
+        self.integration_cycles = cycles;
+        self.config.integration_cycles = cycles;
+        Ok(())
+    }
+
+    /// Set the integration time as a duration instead of a raw cycle count.
+    ///
+    /// The requested duration is rounded to the nearest multiple of the
+    /// 2.78 ms cycle time. Returns [`Error::InvalidInputData`] if it is
+    /// zero or longer than 256 cycles (about 711.7 ms) can represent.
+    pub fn set_integration_time(&mut self, time: Microseconds<u32>) -> Result<(), Error<E>> {
+        let nanos = Nanoseconds::<u64>::from(time);
+        let cycles = Self::duration_to_cycles(nanos, 1)?;
+        self.set_integration_cycles(cycles)
     }
 
     /// Set the RGB converter interrupt clear channel low threshold.
     pub fn set_rgbc_interrupt_low_threshold(&mut self, threshold: u16) -> Result<(), Error<E>> {
         self.write_register(Register::AILTL, threshold as u8)?;
-        self.write_register(Register::AILTH, (threshold >> 8) as u8)
+        self.write_register(Register::AILTH, (threshold >> 8) as u8)?;
+        self.config.rgbc_interrupt_low_threshold = threshold;
+        Ok(())
     }
 
     /// Set the RGB converter interrupt clear channel high threshold.
     pub fn set_rgbc_interrupt_high_threshold(&mut self, threshold: u16) -> Result<(), Error<E>> {
         self.write_register(Register::AIHTL, threshold as u8)?;
-        self.write_register(Register::AIHTH, (threshold >> 8) as u8)
+        self.write_register(Register::AIHTH, (threshold >> 8) as u8)?;
+        self.config.rgbc_interrupt_high_threshold = threshold;
+        Ok(())
     }
 
     /// Set the RGB converter interrupt persistence.
@@ -138,25 +201,12 @@ where
         &mut self,
         persistence: RgbCInterruptPersistence,
     ) -> Result<(), Error<E>> {
-        use crate::RgbCInterruptPersistence as IP;
-        match persistence {
-            IP::Every => self.write_register(Register::APERS, 0),
-            IP::Any => self.write_register(Register::APERS, 1),
-            IP::_2 => self.write_register(Register::APERS, 2),
-            IP::_3 => self.write_register(Register::APERS, 3),
-            IP::_5 => self.write_register(Register::APERS, 4),
-            IP::_10 => self.write_register(Register::APERS, 5),
-            IP::_15 => self.write_register(Register::APERS, 6),
-            IP::_20 => self.write_register(Register::APERS, 7),
-            IP::_25 => self.write_register(Register::APERS, 8),
-            IP::_30 => self.write_register(Register::APERS, 9),
-            IP::_35 => self.write_register(Register::APERS, 10),
-            IP::_40 => self.write_register(Register::APERS, 11),
-            IP::_45 => self.write_register(Register::APERS, 12),
-            IP::_50 => self.write_register(Register::APERS, 13),
-            IP::_55 => self.write_register(Register::APERS, 14),
-            IP::_60 => self.write_register(Register::APERS, 15),
-        }
+        self.write_register(
+            Register::APERS,
+            rgbc_interrupt_persistence_value(persistence),
+        )?;
+        self.config.rgbc_interrupt_persistence = persistence;
+        Ok(())
     }
 
     fn write_register(&mut self, register: u8, value: u8) -> Result<(), Error<E>> {
@@ -165,3 +215,63 @@ where
             .map_err(Error::I2C)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn set_wait_time_rounds_to_nearest_short_cycle_and_disables_wait_long() {
+        // 5560 us rounds to the nearest whole 2.78 ms cycle: 2 cycles.
+        let expected = [
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, 0]),
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::WTIME, 254]),
+        ];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        sensor.set_wait_time(Microseconds(5_560)).unwrap();
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn set_wait_time_enables_wait_long_past_short_cycle_range() {
+        // 800 ms is longer than 256 short cycles (711.68 ms) can represent,
+        // so wait long (x12) must be enabled: 800_000 / (2.78ms * 12)
+        // rounds to 24 cycles.
+        let expected = [
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, BitFlags::WLONG]),
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::WTIME, 232]),
+        ];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        sensor.set_wait_time(Microseconds(800_000)).unwrap();
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn set_wait_time_rejects_duration_too_long_even_with_wait_long() {
+        let mut sensor = Tcs3400::new(I2cMock::new(&[]));
+        let result = sensor.set_wait_time(Microseconds(9_000_000));
+        assert!(matches!(result, Err(Error::InvalidInputData)));
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn set_integration_time_rounds_to_nearest_cycle() {
+        // 2780 us is exactly one integration cycle.
+        let expected = [I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::ATIME, 255],
+        )];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        sensor.set_integration_time(Microseconds(2_780)).unwrap();
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn set_integration_time_rejects_zero_duration() {
+        let mut sensor = Tcs3400::new(I2cMock::new(&[]));
+        let result = sensor.set_integration_time(Microseconds(0));
+        assert!(matches!(result, Err(Error::InvalidInputData)));
+        sensor.destroy().done();
+    }
+}
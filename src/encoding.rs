@@ -0,0 +1,43 @@
+//! Pure register-value encoding shared between the blocking and async
+//! driver implementations, so the two cannot drift apart.
+use crate::{RgbCGain, RgbCInterruptPersistence};
+
+/// Encode the RGB converter gain (`AGAIN` field of the `CONTROL` register).
+pub(crate) fn rgbc_gain_value(gain: RgbCGain) -> u8 {
+    match gain {
+        RgbCGain::_1x => 0,
+        RgbCGain::_4x => 1,
+        RgbCGain::_16x => 2,
+        RgbCGain::_60x => 3,
+    }
+}
+
+/// Encode the RGB converter interrupt persistence (`APERS` field of the
+/// `PERS` register).
+pub(crate) fn rgbc_interrupt_persistence_value(persistence: RgbCInterruptPersistence) -> u8 {
+    use RgbCInterruptPersistence as IP;
+    match persistence {
+        IP::Every => 0,
+        IP::Any => 1,
+        IP::_2 => 2,
+        IP::_3 => 3,
+        IP::_5 => 4,
+        IP::_10 => 5,
+        IP::_15 => 6,
+        IP::_20 => 7,
+        IP::_25 => 8,
+        IP::_30 => 9,
+        IP::_35 => 10,
+        IP::_40 => 11,
+        IP::_45 => 12,
+        IP::_50 => 13,
+        IP::_55 => 14,
+        IP::_60 => 15,
+    }
+}
+
+/// Encode a cycle count (1-256) as the two's-complement register value
+/// shared by `ATIME` and `WTIME`.
+pub(crate) fn cycles_to_register_value(cycles: u16) -> u8 {
+    (256 - cycles) as u8
+}
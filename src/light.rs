@@ -0,0 +1,109 @@
+//! Color temperature and illuminance calculation from raw RGBC channel data.
+//!
+//! This module implements the TAOS/AMS tristimulus conversion used to turn
+//! the clear/red/green/blue channel counts produced by the RGB converter
+//! into a correlated color temperature (in Kelvin) and an illuminance
+//! estimate (in lux). It requires floating point support, so the whole
+//! module is gated behind the `light-conversion` Cargo feature, keeping
+//! `no_std` users who don't need it from paying for the FP code.
+#![cfg(feature = "light-conversion")]
+
+use crate::RgbCChannels;
+
+/// A correlated color temperature and illuminance reading derived from a
+/// set of raw RGBC channel values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorReading {
+    /// Correlated color temperature in Kelvin.
+    pub cct_kelvin: f32,
+    /// Illuminance estimate in lux.
+    pub lux: f32,
+}
+
+/// Calculate the correlated color temperature and illuminance from a set
+/// of raw clear/red/green/blue channel readings.
+///
+/// Returns `None` if the computed tristimulus values `X`, `Y` and `Z` sum
+/// to zero, or if the chromaticity coordinate `y` is exactly `0.1858`
+/// (McCamy's approximation divides by `0.1858 - y`) — both would otherwise
+/// produce a non-finite CCT.
+pub fn calculate_color_temperature_and_lux(channels: &RgbCChannels) -> Option<ColorReading> {
+    let r = f32::from(channels.red);
+    let g = f32::from(channels.green);
+    let b = f32::from(channels.blue);
+
+    let x = -0.14282 * r + 1.54924 * g - 0.95641 * b;
+    let y = -0.32466 * r + 1.57837 * g - 0.73191 * b;
+    let z = -0.68202 * r + 0.77073 * g + 0.56332 * b;
+
+    let sum = x + y + z;
+    if sum == 0.0 {
+        return None;
+    }
+
+    let chromaticity_x = x / sum;
+    let chromaticity_y = y / sum;
+
+    let n_denominator = 0.1858 - chromaticity_y;
+    if n_denominator == 0.0 {
+        return None;
+    }
+
+    let n = (chromaticity_x - 0.3320) / n_denominator;
+    let cct_kelvin = 449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33;
+
+    Some(ColorReading {
+        cct_kelvin,
+        lux: y,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_zero_channels_has_no_reading() {
+        let channels = RgbCChannels {
+            clear: 0,
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+        assert_eq!(calculate_color_temperature_and_lux(&channels), None);
+    }
+
+    #[test]
+    fn near_mccamy_singularity_never_returns_non_finite() {
+        // R:G ratio of about 7.68:1 (B = 0) puts the chromaticity `y`
+        // coordinate right on top of the McCamy constant 0.1858, the
+        // denominator singularity this function must guard against
+        // instead of returning a non-finite CCT.
+        let channels = RgbCChannels {
+            clear: 1000,
+            red: 768,
+            green: 100,
+            blue: 0,
+        };
+        match calculate_color_temperature_and_lux(&channels) {
+            None => {}
+            Some(reading) => {
+                assert!(reading.cct_kelvin.is_finite());
+                assert!(reading.lux.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn balanced_channels_give_a_plausible_daylight_cct() {
+        let channels = RgbCChannels {
+            clear: 1000,
+            red: 300,
+            green: 400,
+            blue: 350,
+        };
+        let reading = calculate_color_temperature_and_lux(&channels).expect("finite reading");
+        assert!(reading.lux > 0.0);
+        assert!(reading.cct_kelvin > 0.0);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{BitFlags, Error, Register, RgbCChannels, Tcs3400, DEVICE_ADDRESS};
+use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::i2c;
+
+/// Maximum number of status polls while waiting for the RGBC-valid bit
+/// before giving up and returning the last reading anyway.
+const MAX_RGBC_VALID_POLLS: u8 = 5;
+
+impl<I2C, E> Tcs3400<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+{
+    /// Take a single RGBC measurement, handling power-on, integration and
+    /// power-off so callers don't have to juggle the enable register
+    /// themselves.
+    ///
+    /// This powers the device on, enables the RGB converter, waits the
+    /// currently configured integration time (see
+    /// [`set_integration_cycles()`](Tcs3400::set_integration_cycles) /
+    /// [`set_integration_time()`](Tcs3400::set_integration_time)), polls
+    /// the RGBC-valid status bit, reads the four channels, and then
+    /// restores whatever power/RGBC-converter state the device was in
+    /// before the call. Handy for battery-powered callers that want a
+    /// low-duty-cycle sampling primitive.
+    pub fn read_one_shot(
+        &mut self,
+        delay: &mut impl DelayMs<u32>,
+    ) -> Result<RgbCChannels, Error<E>> {
+        let was_powered_on = self.enable & BitFlags::POWER_ON != 0;
+        let was_rgbc_on = self.enable & BitFlags::RGBC_EN != 0;
+
+        self.enable()?;
+        self.enable_rgbc()?;
+
+        let integration_time_ms = u32::from(self.integration_cycles) * 278 / 100;
+        delay.delay_ms(integration_time_ms);
+
+        for _ in 0..MAX_RGBC_VALID_POLLS {
+            let status = self.read_register(Register::STATUS)?;
+            if status & BitFlags::RGBC_VALID != 0 {
+                break;
+            }
+            delay.delay_ms(1);
+        }
+
+        let channels = self.read_channels()?;
+
+        if !was_rgbc_on {
+            self.disable_rgbc()?;
+        }
+        if !was_powered_on {
+            self.disable()?;
+        }
+
+        Ok(channels)
+    }
+
+    fn read_channels(&mut self) -> Result<RgbCChannels, Error<E>> {
+        Ok(RgbCChannels {
+            clear: self.read_channel(Register::CDATAL, Register::CDATAH)?,
+            red: self.read_channel(Register::RDATAL, Register::RDATAH)?,
+            green: self.read_channel(Register::GDATAL, Register::GDATAH)?,
+            blue: self.read_channel(Register::BDATAL, Register::BDATAH)?,
+        })
+    }
+
+    fn read_channel(&mut self, low: u8, high: u8) -> Result<u16, Error<E>> {
+        let low = self.read_register(low)?;
+        let high = self.read_register(high)?;
+        Ok(u16::from(low) | (u16::from(high) << 8))
+    }
+
+    fn read_register(&mut self, register: u8) -> Result<u8, Error<E>> {
+        let mut data = [0];
+        self.i2c
+            .write_read(DEVICE_ADDRESS, &[register], &mut data)
+            .map_err(Error::I2C)?;
+        Ok(data[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::delay::MockNoop;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn read_one_shot_powers_on_reads_and_restores_prior_disabled_state() {
+        let expected = [
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::ENABLE, BitFlags::POWER_ON]),
+            I2cTransaction::write(
+                DEVICE_ADDRESS,
+                vec![Register::ENABLE, BitFlags::POWER_ON | BitFlags::RGBC_EN],
+            ),
+            I2cTransaction::write_read(
+                DEVICE_ADDRESS,
+                vec![Register::STATUS],
+                vec![BitFlags::RGBC_VALID],
+            ),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::CDATAL], vec![0x34]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::CDATAH], vec![0x12]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::RDATAL], vec![0x02]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::RDATAH], vec![0x01]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::GDATAL], vec![0x04]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::GDATAH], vec![0x03]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::BDATAL], vec![0x06]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::BDATAH], vec![0x05]),
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::ENABLE, BitFlags::POWER_ON]),
+            I2cTransaction::write(DEVICE_ADDRESS, vec![Register::ENABLE, 0]),
+        ];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        let mut delay = MockNoop::new();
+
+        let channels = sensor.read_one_shot(&mut delay).unwrap();
+
+        assert_eq!(channels.clear, 0x1234);
+        assert_eq!(channels.red, 0x0102);
+        assert_eq!(channels.green, 0x0304);
+        assert_eq!(channels.blue, 0x0506);
+        sensor.destroy().done();
+    }
+
+    #[test]
+    fn read_one_shot_leaves_an_already_enabled_device_powered_on() {
+        let enable_write = I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::ENABLE, BitFlags::POWER_ON],
+        );
+        let enable_rgbc_write = I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::ENABLE, BitFlags::POWER_ON | BitFlags::RGBC_EN],
+        );
+        let expected = [
+            // Setup: device is already powered on and RGBC-enabled.
+            enable_write,
+            enable_rgbc_write.clone(),
+            // read_one_shot() re-applies the same enable/RGBC bits...
+            enable_rgbc_write.clone(),
+            enable_rgbc_write,
+            I2cTransaction::write_read(
+                DEVICE_ADDRESS,
+                vec![Register::STATUS],
+                vec![BitFlags::RGBC_VALID],
+            ),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::CDATAL], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::CDATAH], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::RDATAL], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::RDATAH], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::GDATAL], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::GDATAH], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::BDATAL], vec![0]),
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::BDATAH], vec![0]),
+            // ...and, because it was already on beforehand, leaves it on:
+            // no trailing disable_rgbc()/disable() writes.
+        ];
+        let mut sensor = Tcs3400::new(I2cMock::new(&expected));
+        sensor.enable().unwrap();
+        sensor.enable_rgbc().unwrap();
+
+        let mut delay = MockNoop::new();
+        sensor.read_one_shot(&mut delay).unwrap();
+
+        sensor.destroy().done();
+    }
+}